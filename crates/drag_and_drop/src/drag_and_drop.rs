@@ -1,45 +1,153 @@
 pub mod shared_payloads;
 
-use std::{any::Any, rc::Rc};
+use std::{
+    any::{Any, TypeId},
+    rc::Rc,
+    time::Duration,
+};
 
-use collections::HashSet;
+use collections::{HashMap, HashSet};
 use gpui::{
     elements::{Empty, MouseEventHandler, Overlay},
-    geometry::{rect::RectF, vector::Vector2F},
+    geometry::{
+        rect::RectF,
+        vector::{vec2f, Vector2F},
+    },
     scene::MouseDrag,
     CursorStyle, Element, ElementBox, EventContext, MouseButton, MutableAppContext, RenderContext,
-    View, WeakViewHandle,
+    Task, View, WeakViewHandle,
 };
 
+/// A typical distance in pixels from a scrollable container's edge within which auto-scroll
+/// kicks in, for containers to pass as `edge_width` to `register_scroll_target`/
+/// `as_auto_scroll_target`. Those functions always take the value from their caller.
+pub const AUTO_SCROLL_EDGE_WIDTH: f32 = 32.;
+/// A typical fastest auto-scroll speed, in pixels per tick, reached right at the container's
+/// edge, for containers to pass as `max_speed` to `register_scroll_target`/
+/// `as_auto_scroll_target`. Those functions always take the value from their caller.
+pub const AUTO_SCROLL_MAX_SPEED: f32 = 16.;
+/// How often the auto-scroll task re-applies a scroll delta while the pointer sits near an edge.
+const AUTO_SCROLL_TICK: Duration = Duration::from_millis(16);
+
+/// A point in a drag's lifecycle that interested views can observe via
+/// `DragAndDrop::observe_drag_events`, e.g. for analytics or undo history, instead of relying on
+/// side effects inside `render`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DragEvent {
+    Started { payload_type: TypeId, position: Vector2F },
+    Moved { payload_type: TypeId, position: Vector2F },
+    Canceled { payload_type: TypeId, position: Vector2F },
+    Dropped { payload_type: TypeId, position: Vector2F },
+}
+
+/// Whether dropping the current payload should move it out of its source, or leave the source
+/// intact and copy it to the target. Mirrors the desktop convention of holding Alt/Option to copy
+/// instead of move.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DragOperation {
+    Move,
+    Copy,
+}
+
+impl DragOperation {
+    fn from_modifiers(modifiers: &gpui::keymap_matcher::Modifiers) -> Self {
+        if modifiers.alt {
+            Self::Copy
+        } else {
+            Self::Move
+        }
+    }
+}
+
 enum State<V: View> {
     Dragging {
         window_id: usize,
+        /// Whether this drag is allowed to follow the pointer into other windows, e.g. when
+        /// tearing a tab loose to become its own OS window.
+        cross_window: bool,
+        /// The window the pointer is currently over. Equal to `window_id` unless `cross_window`
+        /// is set and the pointer has since moved into a different window.
+        hovered_window_id: usize,
         position: Vector2F,
         region_offset: Vector2F,
         region: RectF,
         payload: Rc<dyn Any + 'static>,
-        render: Rc<dyn Fn(Rc<dyn Any>, &mut RenderContext<V>) -> ElementBox>,
+        render: Rc<dyn Fn(Rc<dyn Any>, DragOperation, &mut RenderContext<V>) -> ElementBox>,
+        accepting_target: Option<DropTarget<V>>,
+        rejecting_target: bool,
+        operation: DragOperation,
     },
     Canceled,
 }
 
+struct DropTarget<V: View> {
+    region: RectF,
+    payload_type: TypeId,
+    can_accept: Rc<dyn Fn(&dyn Any, DragOperation, &mut MutableAppContext) -> bool>,
+    on_drop: Rc<dyn Fn(Rc<dyn Any>, DragOperation, &mut MutableAppContext)>,
+    _view: std::marker::PhantomData<fn(&V)>,
+}
+
+impl<V: View> Clone for DropTarget<V> {
+    fn clone(&self) -> Self {
+        Self {
+            region: self.region,
+            payload_type: self.payload_type,
+            can_accept: self.can_accept.clone(),
+            on_drop: self.on_drop.clone(),
+            _view: std::marker::PhantomData,
+        }
+    }
+}
+
+/// A scrollable region that should auto-scroll when a drag is held near one of its edges.
+struct ScrollTarget<V: View> {
+    region: RectF,
+    edge_width: f32,
+    max_speed: f32,
+    on_scroll: Rc<dyn Fn(Vector2F, &mut MutableAppContext)>,
+    _view: std::marker::PhantomData<fn(&V)>,
+}
+
+impl<V: View> Clone for ScrollTarget<V> {
+    fn clone(&self) -> Self {
+        Self {
+            region: self.region,
+            edge_width: self.edge_width,
+            max_speed: self.max_speed,
+            on_scroll: self.on_scroll.clone(),
+            _view: std::marker::PhantomData,
+        }
+    }
+}
+
 impl<V: View> Clone for State<V> {
     fn clone(&self) -> Self {
         match self {
             State::Dragging {
                 window_id,
+                cross_window,
+                hovered_window_id,
                 position,
                 region_offset,
                 region,
                 payload,
                 render,
+                accepting_target,
+                rejecting_target,
+                operation,
             } => Self::Dragging {
                 window_id: window_id.clone(),
+                cross_window: *cross_window,
+                hovered_window_id: hovered_window_id.clone(),
                 position: position.clone(),
                 region_offset: region_offset.clone(),
                 region: region.clone(),
                 payload: payload.clone(),
                 render: render.clone(),
+                accepting_target: accepting_target.clone(),
+                rejecting_target: *rejecting_target,
+                operation: *operation,
             },
             State::Canceled => State::Canceled,
         }
@@ -49,6 +157,10 @@ impl<V: View> Clone for State<V> {
 pub struct DragAndDrop<V: View> {
     containers: HashSet<WeakViewHandle<V>>,
     currently_dragged: Option<State<V>>,
+    drop_targets: HashMap<usize, DropTarget<V>>,
+    scroll_targets: HashMap<usize, ScrollTarget<V>>,
+    auto_scroll_task: Option<Task<()>>,
+    drag_event_listeners: Vec<Rc<dyn Fn(DragEvent, &mut MutableAppContext)>>,
 }
 
 impl<V: View> Default for DragAndDrop<V> {
@@ -56,6 +168,10 @@ impl<V: View> Default for DragAndDrop<V> {
         Self {
             containers: Default::default(),
             currently_dragged: Default::default(),
+            drop_targets: Default::default(),
+            scroll_targets: Default::default(),
+            auto_scroll_task: Default::default(),
+            drag_event_listeners: Default::default(),
         }
     }
 }
@@ -65,16 +181,181 @@ impl<V: View> DragAndDrop<V> {
         self.containers.insert(handle);
     }
 
-    pub fn currently_dragged<T: Any>(&self, window_id: usize) -> Option<(Vector2F, Rc<T>)> {
+    /// Subscribes to the drag lifecycle: `Started`, `Moved`, `Canceled`, and `Dropped`. Useful
+    /// for analytics or undo history, which have no other hook into drag state since it otherwise
+    /// only surfaces as side effects inside `render`.
+    pub fn observe_drag_events(
+        &mut self,
+        callback: impl 'static + Fn(DragEvent, &mut MutableAppContext),
+    ) {
+        self.drag_event_listeners.push(Rc::new(callback));
+    }
+
+    fn emit_drag_event(&mut self, event: DragEvent, cx: &mut MutableAppContext) {
+        for listener in self.drag_event_listeners.clone() {
+            listener(event, cx);
+        }
+    }
+
+    /// Registers a region that can accept a drop of payload type `P`. Views should call this
+    /// from `render` every time their layout changes, passing the same `id` they pass to their
+    /// `MouseEventHandler`, so the registration is simply replaced rather than accumulating stale
+    /// entries across frames.
+    pub fn register_drop_target<P: Any>(
+        &mut self,
+        id: usize,
+        region: RectF,
+        can_accept: impl 'static + Fn(&P, DragOperation, &mut MutableAppContext) -> bool,
+        on_drop: impl 'static + Fn(Rc<P>, DragOperation, &mut MutableAppContext),
+    ) {
+        self.drop_targets.insert(
+            id,
+            DropTarget {
+                region,
+                payload_type: TypeId::of::<P>(),
+                can_accept: Rc::new(move |payload, operation, cx| {
+                    payload
+                        .downcast_ref::<P>()
+                        .map_or(false, |payload| can_accept(payload, operation, cx))
+                }),
+                on_drop: Rc::new(move |payload, operation, cx| {
+                    if let Ok(payload) = payload.downcast::<P>() {
+                        on_drop(payload, operation, cx);
+                    }
+                }),
+                _view: std::marker::PhantomData,
+            },
+        );
+    }
+
+    /// Hit-tests `position` against the registered drop targets for `payload`'s type. Returns
+    /// `Some(None)` when the position is over a target that rejected the payload, so callers can
+    /// distinguish "not hovering anything" from "hovering something that won't take this drop".
+    fn drop_target_under(
+        &self,
+        position: Vector2F,
+        payload: &dyn Any,
+        operation: DragOperation,
+        cx: &mut MutableAppContext,
+    ) -> Option<Option<DropTarget<V>>> {
+        let target = self.drop_targets.values().find(|target| {
+            target.payload_type == payload.type_id() && target.region.contains_point(position)
+        })?;
+
+        Some((target.can_accept)(payload, operation, cx).then(|| target.clone()))
+    }
+
+    /// Registers a scrollable region that should auto-scroll when a drag is held within
+    /// `edge_width` pixels of one of its edges, up to `max_speed` pixels per tick right at the
+    /// edge. Like `register_drop_target`, re-register with the same `id` on every `render`.
+    pub fn register_scroll_target(
+        &mut self,
+        id: usize,
+        region: RectF,
+        edge_width: f32,
+        max_speed: f32,
+        on_scroll: impl 'static + Fn(Vector2F, &mut MutableAppContext),
+    ) {
+        self.scroll_targets.insert(
+            id,
+            ScrollTarget {
+                region,
+                edge_width,
+                max_speed,
+                on_scroll: Rc::new(on_scroll),
+                _view: std::marker::PhantomData,
+            },
+        );
+    }
+
+    /// Computes how far, and in which direction, the scroll target under `position` (if any)
+    /// should be scrolled this tick. The magnitude grows linearly from 0 at the target's
+    /// `edge_width` away from an edge up to its `max_speed` right at the edge.
+    fn auto_scroll_delta(&self, position: Vector2F) -> Option<(ScrollTarget<V>, Vector2F)> {
+        let target = self
+            .scroll_targets
+            .values()
+            .find(|target| target.region.contains_point(position))?;
+
+        let speed_for = |distance_from_edge: f32| {
+            if distance_from_edge >= target.edge_width {
+                0.
+            } else {
+                target.max_speed * (1. - distance_from_edge / target.edge_width)
+            }
+        };
+
+        let region = target.region;
+        let dx = speed_for(position.x() - region.min_x()) - speed_for(region.max_x() - position.x());
+        let dy = speed_for(position.y() - region.min_y()) - speed_for(region.max_y() - position.y());
+
+        (dx != 0. || dy != 0.).then(|| (target.clone(), vec2f(dx, dy)))
+    }
+
+    /// Ensures an auto-scroll task is running iff the drag is currently held near a registered
+    /// scroll target's edge, starting one if needed. The task re-reads the drag's position on
+    /// every tick so it keeps scrolling (or stops) as the drag state changes.
+    fn update_auto_scroll(&mut self, cx: &mut MutableAppContext) {
+        let position = match &self.currently_dragged {
+            Some(State::Dragging { position, .. }) => *position,
+            _ => {
+                self.auto_scroll_task.take();
+                return;
+            }
+        };
+
+        if self.auto_scroll_delta(position).is_none() {
+            self.auto_scroll_task.take();
+            return;
+        }
+
+        if self.auto_scroll_task.is_some() {
+            return;
+        }
+
+        self.auto_scroll_task = Some(cx.spawn(|mut cx| async move {
+            loop {
+                cx.background().timer(AUTO_SCROLL_TICK).await;
+
+                let should_continue = cx.update(|cx| {
+                    cx.update_global::<Self, _, _>(|this, cx| {
+                        let position = match &this.currently_dragged {
+                            Some(State::Dragging { position, .. }) => *position,
+                            _ => return false,
+                        };
+
+                        match this.auto_scroll_delta(position) {
+                            Some((target, delta)) => {
+                                (target.on_scroll)(delta, cx);
+                                true
+                            }
+                            None => false,
+                        }
+                    })
+                });
+
+                if !should_continue {
+                    break;
+                }
+            }
+        }));
+    }
+
+    pub fn currently_dragged<T: Any>(
+        &self,
+        window_id: usize,
+    ) -> Option<(Vector2F, Rc<T>, DragOperation)> {
         self.currently_dragged.as_ref().and_then(|state| {
             if let State::Dragging {
                 position,
                 payload,
                 window_id: window_dragged_from,
+                cross_window,
+                operation,
                 ..
             } = state
             {
-                if &window_id != window_dragged_from {
+                if !cross_window && &window_id != window_dragged_from {
                     return None;
                 }
 
@@ -82,7 +363,7 @@ impl<V: View> DragAndDrop<V> {
                     .is::<T>()
                     .then(|| payload.clone().downcast::<T>().ok())
                     .flatten()
-                    .map(|payload| (position.clone(), payload))
+                    .map(|payload| (position.clone(), payload, *operation))
             } else {
                 None
             }
@@ -93,40 +374,126 @@ impl<V: View> DragAndDrop<V> {
         event: MouseDrag,
         payload: Rc<T>,
         cx: &mut EventContext,
-        render: Rc<impl 'static + Fn(&T, &mut RenderContext<V>) -> ElementBox>,
+        render: Rc<impl 'static + Fn(&T, DragOperation, &mut RenderContext<V>) -> ElementBox>,
+    ) {
+        Self::dragging_internal(event, payload, cx, render, false);
+    }
+
+    /// Like `dragging`, but keeps the drag preview and container notifications alive even after
+    /// the pointer leaves the window the drag started in. Pairs with `note_window_hovered` so
+    /// other windows can report that the pointer is now over them, e.g. when tearing a tab loose
+    /// into its own OS window.
+    pub fn dragging_cross_window<T: Any>(
+        event: MouseDrag,
+        payload: Rc<T>,
+        cx: &mut EventContext,
+        render: Rc<impl 'static + Fn(&T, DragOperation, &mut RenderContext<V>) -> ElementBox>,
+    ) {
+        Self::dragging_internal(event, payload, cx, render, true);
+    }
+
+    fn dragging_internal<T: Any>(
+        event: MouseDrag,
+        payload: Rc<T>,
+        cx: &mut EventContext,
+        render: Rc<impl 'static + Fn(&T, DragOperation, &mut RenderContext<V>) -> ElementBox>,
+        cross_window: bool,
     ) {
         let window_id = cx.window_id();
+        let payload_type = TypeId::of::<T>();
+        let position = event.position;
+        let operation = DragOperation::from_modifiers(&event.modifiers);
         cx.update_global::<Self, _, _>(|this, cx| {
-            this.notify_containers_for_window(window_id, cx);
-
+            // A drag that's already been canceled (e.g. via Escape) keeps receiving `MouseDrag`
+            // events until the button is released; don't emit a spurious `Started` for those.
             if matches!(this.currently_dragged, Some(State::Canceled)) {
                 return;
             }
 
-            let (region_offset, region) = if let Some(State::Dragging {
+            let lifecycle_event = if matches!(this.currently_dragged, Some(State::Dragging { .. })) {
+                DragEvent::Moved { payload_type, position }
+            } else {
+                DragEvent::Started { payload_type, position }
+            };
+
+            if cross_window {
+                this.notify_all_containers(lifecycle_event, cx);
+            } else {
+                this.notify_containers_for_window(window_id, lifecycle_event, cx);
+            }
+
+            let (region_offset, region, hovered_window_id) = if let Some(State::Dragging {
                 region_offset,
                 region,
+                hovered_window_id,
                 ..
             }) = this.currently_dragged.as_ref()
             {
-                (*region_offset, *region)
+                (*region_offset, *region, *hovered_window_id)
             } else {
                 (
                     event.region.origin() - event.prev_mouse_position,
                     event.region,
+                    window_id,
                 )
             };
 
+            let hovered_target =
+                this.drop_target_under(event.position, payload.as_ref(), operation, cx);
+            let rejecting_target = matches!(hovered_target, Some(None));
+            let accepting_target = hovered_target.flatten();
+
             this.currently_dragged = Some(State::Dragging {
                 window_id,
+                cross_window,
+                hovered_window_id,
                 region_offset,
                 region,
                 position: event.position,
                 payload,
-                render: Rc::new(move |payload, cx| {
-                    render(payload.downcast_ref::<T>().unwrap(), cx)
+                render: Rc::new(move |payload, operation, cx| {
+                    render(payload.downcast_ref::<T>().unwrap(), operation, cx)
                 }),
+                accepting_target,
+                rejecting_target,
+                operation,
             });
+
+            this.update_auto_scroll(cx);
+        });
+    }
+
+    /// Called by windows other than the one a cross-window drag started in, to report that the
+    /// pointer is now hovering over them. No-op unless a cross-window drag is in progress.
+    pub fn note_window_hovered(window_id: usize, cx: &mut MutableAppContext) {
+        cx.update_global::<Self, _, _>(|this, cx| {
+            let should_notify = matches!(
+                &this.currently_dragged,
+                Some(State::Dragging { cross_window, hovered_window_id, .. })
+                    if *cross_window && *hovered_window_id != window_id
+            );
+
+            if !should_notify {
+                return;
+            }
+
+            let moved_event = if let Some(State::Dragging {
+                hovered_window_id,
+                payload,
+                position,
+                ..
+            }) = &mut this.currently_dragged
+            {
+                *hovered_window_id = window_id;
+                DragEvent::Moved {
+                    payload_type: payload.type_id(),
+                    position: *position,
+                }
+            } else {
+                return;
+            };
+
+            this.notify_all_containers(moved_event, cx);
         });
     }
 
@@ -138,29 +505,38 @@ impl<V: View> DragAndDrop<V> {
             .and_then(|state| {
                 match state {
                     State::Dragging {
-                        window_id,
+                        hovered_window_id,
                         region_offset,
                         position,
                         region,
                         payload,
                         render,
+                        accepting_target: _,
+                        rejecting_target,
+                        operation,
+                        ..
                     } => {
-                        if cx.window_id() != window_id {
+                        // For same-window drags `hovered_window_id` always equals `window_id`; for
+                        // cross-window drags it tracks whichever window the pointer is over.
+                        if cx.window_id() != hovered_window_id {
                             return None;
                         }
 
-                        dbg!("Rendered dragging state");
+                        let cursor_style = if rejecting_target {
+                            CursorStyle::OperationNotAllowed
+                        } else {
+                            CursorStyle::Arrow
+                        };
                         let position = position + region_offset;
                         Some(
                             Overlay::new(
                                 MouseEventHandler::<DraggedElementHandler>::new(0, cx, |_, cx| {
-                                    render(payload, cx)
+                                    render(payload, operation, cx)
                                 })
-                                .with_cursor_style(CursorStyle::Arrow)
+                                .with_cursor_style(cursor_style)
                                 .on_up(MouseButton::Left, |_, cx| {
                                     cx.defer(|cx| {
                                         cx.update_global::<Self, _, _>(|this, cx| {
-                                            dbg!("Up with dragging state");
                                             this.finish_dragging(cx)
                                         });
                                     });
@@ -169,7 +545,6 @@ impl<V: View> DragAndDrop<V> {
                                 .on_up_out(MouseButton::Left, |_, cx| {
                                     cx.defer(|cx| {
                                         cx.update_global::<Self, _, _>(|this, cx| {
-                                            dbg!("Up out with dragging state");
                                             this.finish_dragging(cx)
                                         });
                                     });
@@ -186,60 +561,98 @@ impl<V: View> DragAndDrop<V> {
                         )
                     }
 
-                    State::Canceled => {
-                        dbg!("Rendered canceled state");
-                        Some(
-                            MouseEventHandler::<DraggedElementHandler>::new(0, cx, |_, _| {
-                                Empty::new()
-                                    .constrained()
-                                    .with_width(0.)
-                                    .with_height(0.)
-                                    .boxed()
-                            })
-                            .on_up(MouseButton::Left, |_, cx| {
-                                cx.defer(|cx| {
-                                    cx.update_global::<Self, _, _>(|this, _| {
-                                        dbg!("Up with canceled state");
-                                        this.currently_dragged = None;
-                                    });
+                    State::Canceled => Some(
+                        MouseEventHandler::<DraggedElementHandler>::new(0, cx, |_, _| {
+                            Empty::new()
+                                .constrained()
+                                .with_width(0.)
+                                .with_height(0.)
+                                .boxed()
+                        })
+                        .on_up(MouseButton::Left, |_, cx| {
+                            cx.defer(|cx| {
+                                cx.update_global::<Self, _, _>(|this, _| {
+                                    this.currently_dragged = None;
                                 });
-                            })
-                            .on_up_out(MouseButton::Left, |_, cx| {
-                                cx.defer(|cx| {
-                                    cx.update_global::<Self, _, _>(|this, _| {
-                                        dbg!("Up out with canceled state");
-                                        this.currently_dragged = None;
-                                    });
+                            });
+                        })
+                        .on_up_out(MouseButton::Left, |_, cx| {
+                            cx.defer(|cx| {
+                                cx.update_global::<Self, _, _>(|this, _| {
+                                    this.currently_dragged = None;
                                 });
-                            })
-                            .boxed(),
-                        )
-                    }
+                            });
+                        })
+                        .boxed(),
+                    ),
                 }
             })
     }
 
     pub fn cancel_dragging<P: Any>(&mut self, cx: &mut MutableAppContext) {
         if let Some(State::Dragging {
-            payload, window_id, ..
+            payload,
+            window_id,
+            cross_window,
+            position,
+            ..
         }) = &self.currently_dragged
         {
             if payload.is::<P>() {
                 let window_id = *window_id;
+                let cross_window = *cross_window;
+                let event = DragEvent::Canceled {
+                    payload_type: payload.type_id(),
+                    position: *position,
+                };
                 self.currently_dragged = Some(State::Canceled);
-                dbg!("Canceled");
-                self.notify_containers_for_window(window_id, cx);
+                // Auto-scroll must stop the instant a drag is canceled, not on the task's next tick.
+                self.auto_scroll_task.take();
+                if cross_window {
+                    self.notify_all_containers(event, cx);
+                } else {
+                    self.notify_containers_for_window(window_id, event, cx);
+                }
             }
         }
     }
 
     fn finish_dragging(&mut self, cx: &mut MutableAppContext) {
-        if let Some(State::Dragging { window_id, .. }) = self.currently_dragged.take() {
-            self.notify_containers_for_window(window_id, cx);
+        self.auto_scroll_task.take();
+
+        if let Some(State::Dragging {
+            window_id,
+            cross_window,
+            position,
+            payload,
+            accepting_target,
+            operation,
+            ..
+        }) = self.currently_dragged.take()
+        {
+            let event = DragEvent::Dropped {
+                payload_type: payload.type_id(),
+                position,
+            };
+
+            if let Some(target) = accepting_target {
+                (target.on_drop)(payload, operation, cx);
+            }
+            if cross_window {
+                self.notify_all_containers(event, cx);
+            } else {
+                self.notify_containers_for_window(window_id, event, cx);
+            }
         }
     }
 
-    fn notify_containers_for_window(&mut self, window_id: usize, cx: &mut MutableAppContext) {
+    fn notify_containers_for_window(
+        &mut self,
+        window_id: usize,
+        event: DragEvent,
+        cx: &mut MutableAppContext,
+    ) {
+        self.emit_drag_event(event, cx);
         self.containers.retain(|container| {
             if let Some(container) = container.upgrade(cx) {
                 if container.window_id() == window_id {
@@ -251,13 +664,38 @@ impl<V: View> DragAndDrop<V> {
             }
         });
     }
+
+    /// Like `notify_containers_for_window`, but notifies containers in every window. Used for
+    /// cross-window drags, where the drag can be relevant to a window other than the one the
+    /// `DragAndDrop::<V>::dragging_cross_window` updates are arriving from.
+    fn notify_all_containers(&mut self, event: DragEvent, cx: &mut MutableAppContext) {
+        self.emit_drag_event(event, cx);
+        self.containers.retain(|container| {
+            if let Some(container) = container.upgrade(cx) {
+                container.update(cx, |_, cx| cx.notify());
+                true
+            } else {
+                false
+            }
+        });
+    }
 }
 
 pub trait Draggable {
     fn as_draggable<V: View, P: Any>(
         self,
         payload: P,
-        render: impl 'static + Fn(&P, &mut RenderContext<V>) -> ElementBox,
+        render: impl 'static + Fn(&P, DragOperation, &mut RenderContext<V>) -> ElementBox,
+    ) -> Self
+    where
+        Self: Sized;
+
+    /// Like `as_draggable`, but the drag preview keeps following the pointer even after it
+    /// leaves the window this element lives in, e.g. for tearing a tab loose into its own window.
+    fn as_draggable_cross_window<V: View, P: Any>(
+        self,
+        payload: P,
+        render: impl 'static + Fn(&P, DragOperation, &mut RenderContext<V>) -> ElementBox,
     ) -> Self
     where
         Self: Sized;
@@ -267,7 +705,7 @@ impl<Tag> Draggable for MouseEventHandler<Tag> {
     fn as_draggable<V: View, P: Any>(
         self,
         payload: P,
-        render: impl 'static + Fn(&P, &mut RenderContext<V>) -> ElementBox,
+        render: impl 'static + Fn(&P, DragOperation, &mut RenderContext<V>) -> ElementBox,
     ) -> Self
     where
         Self: Sized,
@@ -280,4 +718,87 @@ impl<Tag> Draggable for MouseEventHandler<Tag> {
             DragAndDrop::<V>::dragging(e, payload, cx, render)
         })
     }
+
+    fn as_draggable_cross_window<V: View, P: Any>(
+        self,
+        payload: P,
+        render: impl 'static + Fn(&P, DragOperation, &mut RenderContext<V>) -> ElementBox,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        let payload = Rc::new(payload);
+        let render = Rc::new(render);
+        self.on_drag(MouseButton::Left, move |e, cx| {
+            let payload = payload.clone();
+            let render = render.clone();
+            DragAndDrop::<V>::dragging_cross_window(e, payload, cx, render)
+        })
+    }
+}
+
+pub trait Droppable {
+    fn as_drop_target<V: View, P: Any>(
+        self,
+        cx: &mut RenderContext<V>,
+        id: usize,
+        region: RectF,
+        can_accept: impl 'static + Fn(&P, DragOperation, &mut MutableAppContext) -> bool,
+        on_drop: impl 'static + Fn(Rc<P>, DragOperation, &mut MutableAppContext),
+    ) -> Self
+    where
+        Self: Sized;
+}
+
+impl<Tag> Droppable for MouseEventHandler<Tag> {
+    fn as_drop_target<V: View, P: Any>(
+        self,
+        cx: &mut RenderContext<V>,
+        id: usize,
+        region: RectF,
+        can_accept: impl 'static + Fn(&P, DragOperation, &mut MutableAppContext) -> bool,
+        on_drop: impl 'static + Fn(Rc<P>, DragOperation, &mut MutableAppContext),
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        cx.update_global::<DragAndDrop<V>, _, _>(|this, _| {
+            this.register_drop_target(id, region, can_accept, on_drop)
+        });
+        self
+    }
+}
+
+pub trait AutoScrollable {
+    fn as_auto_scroll_target<V: View>(
+        self,
+        cx: &mut RenderContext<V>,
+        id: usize,
+        region: RectF,
+        edge_width: f32,
+        max_speed: f32,
+        on_scroll: impl 'static + Fn(Vector2F, &mut MutableAppContext),
+    ) -> Self
+    where
+        Self: Sized;
+}
+
+impl<Tag> AutoScrollable for MouseEventHandler<Tag> {
+    fn as_auto_scroll_target<V: View>(
+        self,
+        cx: &mut RenderContext<V>,
+        id: usize,
+        region: RectF,
+        edge_width: f32,
+        max_speed: f32,
+        on_scroll: impl 'static + Fn(Vector2F, &mut MutableAppContext),
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        cx.update_global::<DragAndDrop<V>, _, _>(|this, _| {
+            this.register_scroll_target(id, region, edge_width, max_speed, on_scroll)
+        });
+        self
+    }
 }